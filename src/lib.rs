@@ -1,76 +1,108 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 
 use avr_hal_generic::hal::{
-    adc::Channel,
+    adc::{Channel, OneShot},
+    blocking::delay::DelayUs,
     digital::v2::{InputPin, OutputPin},
 };
 
+use nb::block;
+
 use ufmt::{uDebug, uDisplay, uWrite, uwrite, Formatter};
 
+use core::convert::Infallible;
+
 use u4::U4;
 
-/// A wrapper for using avr hal devices on a 16 channel analogue multiplexer CD74HC4067 or any other compatible mux
-pub struct Multiplexer<S0, S1, S2, S3, IO, EN> {
-    select0: S0,
-    select1: S1,
-    select2: S2,
-    select3: S3,
+/// A no-op [`DelayUs`] used when no post-select settling delay has been configured. Zero-sized,
+/// so it costs nothing when [`Multiplexer::with_settle_delay`] hasn't been called.
+pub struct NoDelay;
+
+impl DelayUs<u16> for NoDelay {
+    fn delay_us(&mut self, _us: u16) {}
+}
+
+/// A wrapper for using avr hal devices on an N-bit analogue multiplexer: 4-bit 16 channel parts
+/// like the CD74HC4067 (the `N = 4` default), 3-bit 8 channel parts like the CD74HC4051, or
+/// wider addressing formed by cascading chips.
+pub struct Multiplexer<S, IO, EN, D = NoDelay, const N: usize = 4> {
+    select: [S; N],
     io: IO,
     enable: EN,
+    settle_delay: D,
+    settle_us: u16,
 }
 
-impl<S0, S1, S2, S3, IO, EN> Multiplexer<S0, S1, S2, S3, IO, EN> {
-    /// Create a new Multiplexer
+impl<S, IO, EN, const N: usize> Multiplexer<S, IO, EN, NoDelay, N> {
+    /// Create a new Multiplexer from its `N` select pins
     ///
     /// ```
     /// let dp = arduino_uno::Peripherals::take().unwrap();
     /// let mut pins = arduino_uno::Pins::new(dp.PORTB, dp.PORTC, dp.PORTD);
     ///
-    /// // Create a new mux
+    /// // Create a new 4-bit (16 channel) mux
     /// // the enable pin is not bound
     /// let mut mux = Multiplexer::new(
-    ///     pins.d2.into_output(&mut pins.ddr),
-    ///     pins.d3.into_output(&mut pins.ddr),
-    ///     pins.d4.into_output(&mut pins.ddr),
-    ///     pins.d5.into_output(&mut pins.ddr),
+    ///     [
+    ///         pins.d2.into_output(&mut pins.ddr),
+    ///         pins.d3.into_output(&mut pins.ddr),
+    ///         pins.d4.into_output(&mut pins.ddr),
+    ///         pins.d5.into_output(&mut pins.ddr),
+    ///     ],
     ///     pins.a0.into_output(&mut pins.ddr),
     ///     ()
     /// );
     /// ```
-    pub fn new(select0: S0, select1: S1, select2: S2, select3: S3, io: IO, enable: EN) -> Self {
+    pub fn new(select: [S; N], io: IO, enable: EN) -> Self {
         Self {
-            select0,
-            select1,
-            select2,
-            select3,
+            select,
             io,
             enable,
+            settle_delay: NoDelay,
+            settle_us: 0,
         }
     }
+}
 
-    /// Select a channel
-    pub fn select(
+impl<S, IO, EN, D, const N: usize> Multiplexer<S, IO, EN, D, N> {
+    /// Attach a post-select settling delay: after driving the select lines, `select_channel`
+    /// will sleep for `us` microseconds before returning, so the mux output has settled before
+    /// the caller reads or writes the shared `io` line. Needed because the CD74HC4067 has a
+    /// non-zero channel-switch propagation time.
+    pub fn with_settle_delay<D2: DelayUs<u16>>(
+        self,
+        delay: D2,
+        us: u16,
+    ) -> Multiplexer<S, IO, EN, D2, N> {
+        Multiplexer {
+            select: self.select,
+            io: self.io,
+            enable: self.enable,
+            settle_delay: delay,
+            settle_us: us,
+        }
+    }
+
+    /// Select a channel, addressed by a plain `u16` bounds-checked against the `2^N` channels
+    /// this mux can address.
+    pub fn select_channel(
         &mut self,
-        selection: U4,
-    ) -> Result<(), MultiplexSelectionError<S0::Error, S1::Error, S2::Error, S3::Error>>
+        channel: u16,
+    ) -> Result<(), MultiplexSelectionError<S::Error>>
     where
-        S0: OutputPin,
-        S1: OutputPin,
-        S2: OutputPin,
-        S3: OutputPin,
+        S: OutputPin,
+        D: DelayUs<u16>,
     {
-        let selection: u16 = selection.into();
-        set_pin(&mut self.select0, (selection >> 0 & 1) != 0)
-            .map_err(MultiplexSelectionError::Select0)?;
-
-        set_pin(&mut self.select1, (selection >> 1 & 1) != 0)
-            .map_err(MultiplexSelectionError::Select1)?;
+        if u32::from(channel) >= (1u32 << N) {
+            return Err(MultiplexSelectionError::OutOfRange(channel));
+        }
 
-        set_pin(&mut self.select2, (selection >> 2 & 1) != 0)
-            .map_err(MultiplexSelectionError::Select2)?;
+        for (i, pin) in self.select.iter_mut().enumerate() {
+            let bit = (channel >> i & 1) != 0;
+            set_pin(pin, bit).map_err(|e| MultiplexSelectionError::Select(i, e))?;
+        }
 
-        set_pin(&mut self.select3, (selection >> 3 & 1) != 0)
-            .map_err(MultiplexSelectionError::Select3)?;
+        self.settle_delay.delay_us(self.settle_us);
 
         Ok(())
     }
@@ -83,6 +115,18 @@ impl<S0, S1, S2, S3, IO, EN> Multiplexer<S0, S1, S2, S3, IO, EN> {
         self.enable.set_low()
     }
 
+    /// Infallible version of [`enable`](Self::enable), for the common case where the enable
+    /// pin's `OutputPin::Error` is [`Infallible`].
+    pub fn enable_infallible(&mut self)
+    where
+        EN: OutputPin<Error = Infallible>,
+    {
+        match self.enable() {
+            Ok(()) => {}
+            Err(e) => match e {},
+        }
+    }
+
     /// Disable selection pins. EN pin is disable high
     pub fn disable(&mut self) -> Result<(), EN::Error>
     where
@@ -90,6 +134,89 @@ impl<S0, S1, S2, S3, IO, EN> Multiplexer<S0, S1, S2, S3, IO, EN> {
     {
         self.enable.set_high()
     }
+
+    /// Infallible version of [`disable`](Self::disable), for the common case where the enable
+    /// pin's `OutputPin::Error` is [`Infallible`].
+    pub fn disable_infallible(&mut self)
+    where
+        EN: OutputPin<Error = Infallible>,
+    {
+        match self.disable() {
+            Ok(()) => {}
+            Err(e) => match e {},
+        }
+    }
+}
+
+/// The `N == 4` specialization: the CD74HC4067-compatible 16 channel mux, addressed by [`U4`]
+/// rather than a bounds-checked `u16`.
+impl<S, IO, EN, D> Multiplexer<S, IO, EN, D, 4> {
+    /// Select a channel
+    pub fn select(&mut self, selection: U4) -> Result<(), MultiplexSelectionError<S::Error>>
+    where
+        S: OutputPin,
+        D: DelayUs<u16>,
+    {
+        self.select_channel(selection.into())
+    }
+
+    /// Infallible version of [`select`](Self::select), for the common case where the select
+    /// pins' `OutputPin::Error` is [`Infallible`] and the caller doesn't want to `unwrap()` on
+    /// every call in a blink/scan loop.
+    pub fn select_infallible(&mut self, selection: U4)
+    where
+        S: OutputPin<Error = Infallible>,
+        D: DelayUs<u16>,
+    {
+        match self.select(selection) {
+            Ok(()) => {}
+            Err(MultiplexSelectionError::Select(_, e)) => match e {},
+            Err(MultiplexSelectionError::OutOfRange(_)) => {
+                unreachable!("U4 is always within range for a 4-bit mux")
+            }
+        }
+    }
+
+    /// Read every one of the 16 channels in turn, selecting each in order and taking a
+    /// one-shot ADC conversion through `self` (via the [`Channel`] impl delegating to `io`).
+    ///
+    /// This is the common "read the whole analog mux" use case collapsed into a single call,
+    /// rather than the caller having to loop, re-select and convert by hand.
+    pub fn scan_adc<ADC, A>(
+        &mut self,
+        adc: &mut A,
+    ) -> Result<[u16; 16], ScanError<S::Error, A::Error>>
+    where
+        S: OutputPin,
+        D: DelayUs<u16>,
+        IO: Channel<ADC>,
+        A: OneShot<ADC, u16, Self>,
+    {
+        let mut buf = [0u16; 16];
+        self.scan_adc_into(adc, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Like [`scan_adc`](Self::scan_adc), but writes into a caller-supplied buffer instead of
+    /// returning an owned array, for callers that want to avoid the extra stack allocation.
+    pub fn scan_adc_into<ADC, A>(
+        &mut self,
+        adc: &mut A,
+        buf: &mut [u16; 16],
+    ) -> Result<(), ScanError<S::Error, A::Error>>
+    where
+        S: OutputPin,
+        D: DelayUs<u16>,
+        IO: Channel<ADC>,
+        A: OneShot<ADC, u16, Self>,
+    {
+        for selection in U4::all() {
+            self.select(selection).map_err(ScanError::Select)?;
+            let channel: u16 = selection.into();
+            buf[channel as usize] = block!(adc.read(self)).map_err(ScanError::Adc)?;
+        }
+        Ok(())
+    }
 }
 
 fn set_pin<PIN: OutputPin>(pin: &mut PIN, on: bool) -> Result<(), PIN::Error> {
@@ -100,7 +227,7 @@ fn set_pin<PIN: OutputPin>(pin: &mut PIN, on: bool) -> Result<(), PIN::Error> {
     }
 }
 
-impl<S0, S1, S2, S3, IO, EN> OutputPin for Multiplexer<S0, S1, S2, S3, IO, EN>
+impl<S, IO, EN, D, const N: usize> OutputPin for Multiplexer<S, IO, EN, D, N>
 where
     IO: OutputPin,
 {
@@ -115,7 +242,7 @@ where
     }
 }
 
-impl<S0, S1, S2, S3, IO, EN> InputPin for Multiplexer<S0, S1, S2, S3, IO, EN>
+impl<S, IO, EN, D, const N: usize> InputPin for Multiplexer<S, IO, EN, D, N>
 where
     IO: InputPin,
 {
@@ -130,7 +257,7 @@ where
     }
 }
 
-impl<ADC, S0, S1, S2, S3, IO, EN> Channel<ADC> for Multiplexer<S0, S1, S2, S3, IO, EN>
+impl<ADC, S, IO, EN, D, const N: usize> Channel<ADC> for Multiplexer<S, IO, EN, D, N>
 where
     IO: Channel<ADC>,
 {
@@ -141,57 +268,310 @@ where
     }
 }
 
-pub enum MultiplexSelectionError<E0, E1, E2, E3> {
-    Select0(E0),
-    Select1(E1),
-    Select2(E2),
-    Select3(E3),
+/// Error selecting a channel: either the requested channel doesn't fit within the `2^N`
+/// channels this mux can address, or driving one of the select pins (named by index) failed.
+pub enum MultiplexSelectionError<E> {
+    OutOfRange(u16),
+    Select(usize, E),
+}
+
+impl<E> uDebug for MultiplexSelectionError<E>
+where
+    E: uDebug,
+{
+    fn fmt<W: ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
+    where
+        W: uWrite,
+    {
+        match self {
+            MultiplexSelectionError::OutOfRange(channel) => {
+                uwrite!(f, "OutOfRange({:?})", channel)
+            }
+            MultiplexSelectionError::Select(i, e) => uwrite!(f, "Select({:?}, {:?})", i, e),
+        }
+    }
+}
+
+impl<E> uDisplay for MultiplexSelectionError<E>
+where
+    E: uDisplay,
+{
+    fn fmt<W: ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
+    where
+        W: uWrite,
+    {
+        match self {
+            MultiplexSelectionError::OutOfRange(channel) => {
+                uwrite!(f, "OutOfRange({})", channel)
+            }
+            MultiplexSelectionError::Select(i, e) => uwrite!(f, "Select({}, {})", i, e),
+        }
+    }
+}
+
+/// Error from [`Multiplexer::scan_adc`], unifying a channel-selection failure with a failure of
+/// the ADC conversion itself.
+pub enum ScanError<E, ADCE> {
+    Select(MultiplexSelectionError<E>),
+    Adc(ADCE),
 }
 
-impl<E0, E1, E2, E3> uDebug for MultiplexSelectionError<E0, E1, E2, E3>
+impl<E, ADCE> uDebug for ScanError<E, ADCE>
 where
-    E0: uDebug,
-    E1: uDebug,
-    E2: uDebug,
-    E3: uDebug,
+    E: uDebug,
+    ADCE: uDebug,
 {
     fn fmt<W: ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
     where
         W: uWrite,
     {
-        use MultiplexSelectionError::*;
         match self {
-            Select0(e) => uwrite!(f, "Select0({:?})", e),
-            Select1(e) => uwrite!(f, "Select1({:?})", e),
-            Select2(e) => uwrite!(f, "Select2({:?})", e),
-            Select3(e) => uwrite!(f, "Select3({:?})", e),
+            ScanError::Select(e) => uwrite!(f, "Select({:?})", e),
+            ScanError::Adc(e) => uwrite!(f, "Adc({:?})", e),
         }
     }
 }
 
-impl<E0, E1, E2, E3> uDisplay for MultiplexSelectionError<E0, E1, E2, E3>
+impl<E, ADCE> uDisplay for ScanError<E, ADCE>
 where
-    E0: uDisplay,
-    E1: uDisplay,
-    E2: uDisplay,
-    E3: uDisplay,
+    E: uDisplay,
+    ADCE: uDisplay,
 {
     fn fmt<W: ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
     where
         W: uWrite,
     {
-        use MultiplexSelectionError::*;
         match self {
-            Select0(e) => uwrite!(f, "Select0({})", e),
-            Select1(e) => uwrite!(f, "Select1({})", e),
-            Select2(e) => uwrite!(f, "Select2({})", e),
-            Select3(e) => uwrite!(f, "Select3({})", e),
+            ScanError::Select(e) => uwrite!(f, "Select({})", e),
+            ScanError::Adc(e) => uwrite!(f, "Adc({})", e),
+        }
+    }
+}
+
+/// Coordinates `K` [`Multiplexer`] chips that share a single `io`/ADC line, each gated by its
+/// own active-low `EN` pin (as modelled by [`Multiplexer::enable`]/[`Multiplexer::disable`]),
+/// so a caller can treat them as one logical `2^N * K` channel device. Only one chip is ever
+/// enabled at a time, so all `K` multiplexers may alias the same physical `io` pin; the bus's own
+/// `OutputPin`/`InputPin` impls always go through the first mux's `io`, whichever chip is
+/// selected.
+pub struct MultiplexerBus<S, IO, EN, D, const N: usize, const K: usize> {
+    muxes: [Multiplexer<S, IO, EN, D, N>; K],
+}
+
+impl<S, IO, EN, D, const N: usize, const K: usize> MultiplexerBus<S, IO, EN, D, N, K> {
+    /// Create a new bus from `K` already-constructed multiplexers
+    pub fn new(muxes: [Multiplexer<S, IO, EN, D, N>; K]) -> Self {
+        Self { muxes }
+    }
+
+    /// Select a global channel in `0..2^N * K`: disables every chip but the one addressed,
+    /// sets that chip's local address, and then enables it.
+    pub fn select_channel(
+        &mut self,
+        channel: u16,
+    ) -> Result<(), MultiplexBusError<S::Error, EN::Error>>
+    where
+        S: OutputPin,
+        EN: OutputPin,
+        D: DelayUs<u16>,
+    {
+        let channels_per_chip = 1u32 << N;
+        let channel32 = u32::from(channel);
+        let chip = (channel32 / channels_per_chip) as usize;
+        let local = (channel32 % channels_per_chip) as u16;
+
+        if chip >= K {
+            return Err(MultiplexBusError::OutOfRange(channel));
+        }
+
+        // Disable every other chip first, so the target is never enabled alongside one still
+        // driving the shared io/ADC line.
+        for (i, mux) in self.muxes.iter_mut().enumerate() {
+            if i != chip {
+                mux.disable().map_err(MultiplexBusError::Enable)?;
+            }
+        }
+
+        let target = &mut self.muxes[chip];
+        target.select_channel(local).map_err(MultiplexBusError::Select)?;
+        target.enable().map_err(MultiplexBusError::Enable)?;
+
+        Ok(())
+    }
+
+    /// Read every channel on every chip in turn, selecting each global channel and taking a
+    /// one-shot ADC conversion on the shared line, writing `2^N * K` samples into `buf`.
+    pub fn scan_adc_into<ADC, A>(
+        &mut self,
+        adc: &mut A,
+        buf: &mut [u16],
+    ) -> Result<(), MultiplexBusScanError<S::Error, EN::Error, A::Error>>
+    where
+        S: OutputPin,
+        EN: OutputPin,
+        D: DelayUs<u16>,
+        IO: Channel<ADC>,
+        A: OneShot<ADC, u16, Multiplexer<S, IO, EN, D, N>>,
+    {
+        let channels_per_chip = 1u32 << N;
+        let total = channels_per_chip as usize * K;
+
+        if buf.len() < total {
+            return Err(MultiplexBusScanError::BufferTooShort(total));
+        }
+
+        // Iterate the global channel index as `u32`, since `total` (and therefore the last
+        // index) can exceed `u16::MAX` once `N == 16`; `select_channel`'s `u16` parameter still
+        // covers every channel a caller can actually reach through it.
+        for channel in 0..total as u32 {
+            self.select_channel(channel as u16)
+                .map_err(MultiplexBusScanError::Select)?;
+            let chip = (channel / channels_per_chip) as usize;
+            buf[channel as usize] =
+                block!(adc.read(&mut self.muxes[chip])).map_err(MultiplexBusScanError::Adc)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S, IO, EN, D, const N: usize, const K: usize> OutputPin for MultiplexerBus<S, IO, EN, D, N, K>
+where
+    IO: OutputPin,
+{
+    type Error = IO::Error;
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.muxes[0].set_high()
+    }
+
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.muxes[0].set_low()
+    }
+}
+
+impl<S, IO, EN, D, const N: usize, const K: usize> InputPin for MultiplexerBus<S, IO, EN, D, N, K>
+where
+    IO: InputPin,
+{
+    type Error = IO::Error;
+
+    fn is_high(&self) -> Result<bool, Self::Error> {
+        self.muxes[0].is_high()
+    }
+
+    fn is_low(&self) -> Result<bool, Self::Error> {
+        self.muxes[0].is_low()
+    }
+}
+
+impl<ADC, S, IO, EN, D, const N: usize, const K: usize> Channel<ADC>
+    for MultiplexerBus<S, IO, EN, D, N, K>
+where
+    IO: Channel<ADC>,
+{
+    type ID = IO::ID;
+
+    fn channel() -> Self::ID {
+        IO::channel()
+    }
+}
+
+/// Error selecting a global channel on a [`MultiplexerBus`]: either the channel doesn't address
+/// any of the `K` chips on the bus, or selecting/enabling a chip failed.
+pub enum MultiplexBusError<SE, ENE> {
+    OutOfRange(u16),
+    Select(MultiplexSelectionError<SE>),
+    Enable(ENE),
+}
+
+impl<SE, ENE> uDebug for MultiplexBusError<SE, ENE>
+where
+    SE: uDebug,
+    ENE: uDebug,
+{
+    fn fmt<W: ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
+    where
+        W: uWrite,
+    {
+        match self {
+            MultiplexBusError::OutOfRange(channel) => uwrite!(f, "OutOfRange({:?})", channel),
+            MultiplexBusError::Select(e) => uwrite!(f, "Select({:?})", e),
+            MultiplexBusError::Enable(e) => uwrite!(f, "Enable({:?})", e),
+        }
+    }
+}
+
+impl<SE, ENE> uDisplay for MultiplexBusError<SE, ENE>
+where
+    SE: uDisplay,
+    ENE: uDisplay,
+{
+    fn fmt<W: ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
+    where
+        W: uWrite,
+    {
+        match self {
+            MultiplexBusError::OutOfRange(channel) => uwrite!(f, "OutOfRange({})", channel),
+            MultiplexBusError::Select(e) => uwrite!(f, "Select({})", e),
+            MultiplexBusError::Enable(e) => uwrite!(f, "Enable({})", e),
+        }
+    }
+}
+
+/// Error from [`MultiplexerBus::scan_adc_into`], unifying a channel-selection failure with a
+/// failure of the ADC conversion itself.
+pub enum MultiplexBusScanError<SE, ENE, ADCE> {
+    /// `buf` was shorter than the `2^N * K` samples a full scan writes; carries the required
+    /// length.
+    BufferTooShort(usize),
+    Select(MultiplexBusError<SE, ENE>),
+    Adc(ADCE),
+}
+
+impl<SE, ENE, ADCE> uDebug for MultiplexBusScanError<SE, ENE, ADCE>
+where
+    SE: uDebug,
+    ENE: uDebug,
+    ADCE: uDebug,
+{
+    fn fmt<W: ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
+    where
+        W: uWrite,
+    {
+        match self {
+            MultiplexBusScanError::BufferTooShort(required) => {
+                uwrite!(f, "BufferTooShort({:?})", required)
+            }
+            MultiplexBusScanError::Select(e) => uwrite!(f, "Select({:?})", e),
+            MultiplexBusScanError::Adc(e) => uwrite!(f, "Adc({:?})", e),
+        }
+    }
+}
+
+impl<SE, ENE, ADCE> uDisplay for MultiplexBusScanError<SE, ENE, ADCE>
+where
+    SE: uDisplay,
+    ENE: uDisplay,
+    ADCE: uDisplay,
+{
+    fn fmt<W: ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
+    where
+        W: uWrite,
+    {
+        match self {
+            MultiplexBusScanError::BufferTooShort(required) => {
+                uwrite!(f, "BufferTooShort({})", required)
+            }
+            MultiplexBusScanError::Select(e) => uwrite!(f, "Select({})", e),
+            MultiplexBusScanError::Adc(e) => uwrite!(f, "Adc({})", e),
         }
     }
 }
 
 pub mod u4 {
     use core::convert::TryFrom;
+    use core::ops::{BitAnd, BitOr, BitXor};
     use ufmt::{uDisplay, uWrite, uwrite, Formatter};
 
     #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
@@ -217,8 +597,42 @@ pub mod u4 {
         pub const FOURTEEN: U4 = U4(14);
         pub const FIFTEEN: U4 = U4(15);
 
+        /// Truncate `val` to four bits by masking off everything but the low nibble, so every
+        /// value 0-15 is reachable (as opposed to `val % 15`, which can never produce 15).
         pub fn truncated(val: u16) -> U4 {
-            U4(val % U4::MAX.0)
+            U4(val & 0xF)
+        }
+
+        /// The next channel, wrapping from `FIFTEEN` back to `ZERO`
+        pub fn next(self) -> U4 {
+            U4::truncated(self.0 + 1)
+        }
+
+        /// The previous channel, wrapping from `ZERO` back to `FIFTEEN`
+        pub fn prev(self) -> U4 {
+            U4::truncated(self.0.wrapping_sub(1))
+        }
+
+        /// An iterator over every channel, `ZERO..=FIFTEEN`, for scanning all 16 in sequence
+        pub fn all() -> All {
+            All(Some(U4::ZERO))
+        }
+    }
+
+    /// Iterator returned by [`U4::all`]
+    pub struct All(Option<U4>);
+
+    impl Iterator for All {
+        type Item = U4;
+
+        fn next(&mut self) -> Option<U4> {
+            let current = self.0?;
+            self.0 = if current == U4::MAX {
+                None
+            } else {
+                Some(U4(current.0 + 1))
+            };
+            Some(current)
         }
     }
 
@@ -228,6 +642,12 @@ pub mod u4 {
         }
     }
 
+    impl From<u8> for U4 {
+        fn from(val: u8) -> U4 {
+            U4::truncated(val as u16)
+        }
+    }
+
     impl TryFrom<u16> for U4 {
         type Error = ();
         fn try_from(val: u16) -> Result<U4, Self::Error> {
@@ -239,6 +659,27 @@ pub mod u4 {
         }
     }
 
+    impl BitAnd for U4 {
+        type Output = U4;
+        fn bitand(self, rhs: U4) -> U4 {
+            U4::truncated(self.0 & rhs.0)
+        }
+    }
+
+    impl BitOr for U4 {
+        type Output = U4;
+        fn bitor(self, rhs: U4) -> U4 {
+            U4::truncated(self.0 | rhs.0)
+        }
+    }
+
+    impl BitXor for U4 {
+        type Output = U4;
+        fn bitxor(self, rhs: U4) -> U4 {
+            U4::truncated(self.0 ^ rhs.0)
+        }
+    }
+
     impl uDisplay for U4 {
         fn fmt<W: ?Sized>(&self, f: &mut Formatter<W>) -> Result<(), W::Error>
         where
@@ -248,3 +689,158 @@ pub mod u4 {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::vec::Vec;
+
+    #[derive(Default, Copy, Clone)]
+    struct MockPin {
+        high: bool,
+    }
+
+    impl OutputPin for MockPin {
+        type Error = Infallible;
+
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            self.high = true;
+            Ok(())
+        }
+
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            self.high = false;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn u4_all_yields_zero_through_fifteen() {
+        let channels: Vec<u16> = U4::all().map(u16::from).collect();
+        assert_eq!(channels, (0..=15).collect::<Vec<u16>>());
+    }
+
+    #[test]
+    fn u4_truncated_masks_low_nibble_so_fifteen_is_reachable() {
+        assert_eq!(u16::from(U4::truncated(15)), 15);
+        assert_eq!(u16::from(U4::truncated(16)), 0);
+    }
+
+    #[test]
+    fn u4_next_wraps_from_fifteen_to_zero() {
+        assert_eq!(u16::from(U4::FIFTEEN.next()), u16::from(U4::ZERO));
+    }
+
+    #[test]
+    fn u4_prev_wraps_from_zero_to_fifteen() {
+        assert_eq!(u16::from(U4::ZERO.prev()), u16::from(U4::FIFTEEN));
+    }
+
+    fn mux() -> Multiplexer<MockPin, MockPin, MockPin> {
+        Multiplexer::new(
+            [
+                MockPin::default(),
+                MockPin::default(),
+                MockPin::default(),
+                MockPin::default(),
+            ],
+            MockPin::default(),
+            MockPin::default(),
+        )
+    }
+
+    #[test]
+    fn select_channel_rejects_out_of_range() {
+        let mut mux = mux();
+        assert!(matches!(
+            mux.select_channel(16),
+            Err(MultiplexSelectionError::OutOfRange(16))
+        ));
+    }
+
+    #[test]
+    fn select_channel_accepts_max_in_range_value() {
+        let mut mux = mux();
+        assert!(mux.select_channel(15).is_ok());
+    }
+
+    #[test]
+    fn select_channel_with_n_16_does_not_panic() {
+        let mut mux: Multiplexer<MockPin, MockPin, MockPin, NoDelay, 16> =
+            Multiplexer::new([MockPin::default(); 16], MockPin::default(), MockPin::default());
+        assert!(mux.select_channel(u16::MAX).is_ok());
+    }
+
+    #[derive(Clone)]
+    struct LoggingEnablePin {
+        id: usize,
+        log: Rc<RefCell<Vec<(usize, bool)>>>,
+    }
+
+    impl OutputPin for LoggingEnablePin {
+        type Error = Infallible;
+
+        fn set_high(&mut self) -> Result<(), Infallible> {
+            self.log.borrow_mut().push((self.id, true));
+            Ok(())
+        }
+
+        fn set_low(&mut self) -> Result<(), Infallible> {
+            self.log.borrow_mut().push((self.id, false));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn bus_select_channel_disables_previous_chip_before_enabling_next() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let make_mux = |id: usize| {
+            Multiplexer::new(
+                [
+                    MockPin::default(),
+                    MockPin::default(),
+                    MockPin::default(),
+                    MockPin::default(),
+                ],
+                MockPin::default(),
+                LoggingEnablePin {
+                    id,
+                    log: log.clone(),
+                },
+            )
+        };
+        let mut bus = MultiplexerBus::new([make_mux(0), make_mux(1)]);
+
+        bus.select_channel(0).unwrap();
+        log.borrow_mut().clear();
+        bus.select_channel(16).unwrap();
+
+        let entries = log.borrow();
+        let disable_0 = entries
+            .iter()
+            .position(|&(id, high)| id == 0 && high)
+            .expect("chip 0 was disabled");
+        let enable_1 = entries
+            .iter()
+            .position(|&(id, high)| id == 1 && !high)
+            .expect("chip 1 was enabled");
+        assert!(
+            disable_0 < enable_1,
+            "chip 1 was enabled before chip 0 was disabled: {:?}",
+            *entries
+        );
+    }
+
+    #[test]
+    fn bus_select_channel_with_n_16_does_not_panic() {
+        let mut bus: MultiplexerBus<MockPin, MockPin, MockPin, NoDelay, 16, 1> =
+            MultiplexerBus::new([Multiplexer::new(
+                [MockPin::default(); 16],
+                MockPin::default(),
+                MockPin::default(),
+            )]);
+        assert!(bus.select_channel(u16::MAX).is_ok());
+    }
+}